@@ -1,10 +1,10 @@
-use std::{borrow::Cow, ops::RangeInclusive};
+use std::{borrow::Cow, collections::HashMap, ops::RangeInclusive};
 
 use fastnbt::LongArray;
 
 use crate::{
     schema,
-    util::{UVec3, Vec3},
+    util::{Axis, UVec3, Vec3},
     BlockState, Entity, Litematic, TileEntity,
 };
 
@@ -140,6 +140,209 @@ impl<'l> Region<'l> {
         self.blocks[blocks_idx] = palette_idx;
     }
 
+    /// Rotates the region `quarter_turns` times by 90° about the Y axis.
+    pub fn rotate_y(&mut self, quarter_turns: i32) {
+        for _ in 0..quarter_turns.rem_euclid(4) {
+            let s = self.size();
+            let new_size = UVec3::new(s.z, s.y, s.x);
+            self.remap_cells(new_size, |p| UVec3::new(s.z - 1 - p.z, p.y, p.x));
+            for state in &mut self.palette {
+                rotate_block_state_y(state);
+            }
+        }
+    }
+
+    /// Mirrors the region across the plane perpendicular to `axis`.
+    pub fn mirror(&mut self, axis: Axis) {
+        let s = self.size();
+        match axis {
+            Axis::X => self.remap_cells(s, |p| UVec3 { x: s.x - 1 - p.x, ..p }),
+            Axis::Y => self.remap_cells(s, |p| UVec3 { y: s.y - 1 - p.y, ..p }),
+            Axis::Z => self.remap_cells(s, |p| UVec3 { z: s.z - 1 - p.z, ..p }),
+        }
+        for state in &mut self.palette {
+            mirror_block_state(state, axis);
+        }
+    }
+
+    /// Shifts the region by `offset`, moving the corners and world-absolute entities.
+    pub fn translate(&mut self, offset: Vec3) {
+        self.corner1 = self.corner1 + offset;
+        self.corner2 = self.corner2 + offset;
+        for entity in &mut self.entities {
+            entity.position.x += offset.x as f64;
+            entity.position.y += offset.y as f64;
+            entity.position.z += offset.z as f64;
+        }
+    }
+
+    /// Rebuilds `blocks` and tile entity positions for a new size by sending each cell's
+    /// region-local coordinates through `map`, keeping the minimum corner fixed.
+    fn remap_cells<F>(&mut self, new_size: UVec3, map: F)
+    where
+        F: Fn(UVec3) -> UVec3,
+    {
+        let old_size = self.size();
+        let origin = self.blocks_origin();
+        let mut blocks = vec![0usize; new_size.volume() as usize];
+        for (i, &palette_idx) in self.blocks.iter().enumerate() {
+            let dst = map(cell_to_local(i, old_size));
+            blocks[local_to_cell(dst, new_size)] = palette_idx;
+        }
+        self.blocks = blocks;
+
+        for te in &mut self.tile_entities {
+            te.pos = map(te.pos);
+        }
+
+        self.corner1 = origin;
+        self.corner2 = origin + UVec3::new(new_size.x - 1, new_size.y - 1, new_size.z - 1);
+    }
+
+    /// Rebuilds the palette to drop dead and duplicate entries, keeping air at index 0.
+    pub fn optimize_palette(&mut self) {
+        let mut palette = vec![block!()];
+        let mut remap: Vec<Option<usize>> = vec![None; self.palette.len()];
+        for &idx in &self.blocks {
+            if remap[idx].is_some() {
+                continue;
+            }
+            let state = &self.palette[idx];
+            let new_idx = match palette.iter().position(|b| b == state) {
+                Some(pos) => pos,
+                None => {
+                    palette.push(state.clone());
+                    palette.len() - 1
+                }
+            };
+            remap[idx] = Some(new_idx);
+        }
+        for b in &mut self.blocks {
+            *b = remap[*b].expect("every referenced palette index is remapped");
+        }
+        self.palette = palette;
+    }
+
+    /// Sets every position in the inclusive box from `corner1` to `corner2` to `block`.
+    pub fn fill(&mut self, corner1: Vec3, corner2: Vec3, block: BlockState<'l>) {
+        let palette_idx = match self.palette.iter().position(|b| b == &block) {
+            Some(idx) => idx,
+            None => {
+                self.palette.push(block);
+                self.palette.len() - 1
+            }
+        };
+        for y in corner1.y.min(corner2.y)..=corner1.y.max(corner2.y) {
+            for z in corner1.z.min(corner2.z)..=corner1.z.max(corner2.z) {
+                for x in corner1.x.min(corner2.x)..=corner1.x.max(corner2.x) {
+                    let idx = self.pos_to_index(Vec3::new(x, y, z));
+                    self.blocks[idx] = palette_idx;
+                }
+            }
+        }
+    }
+
+    /// Replaces every occurrence of the `from` block state with `to`.
+    pub fn replace(&mut self, from: &BlockState, to: BlockState<'l>) {
+        let from_idx = match self.palette.iter().position(|b| b == from) {
+            Some(idx) => idx,
+            None => return,
+        };
+        let to_idx = match self.palette.iter().position(|b| b == &to) {
+            Some(idx) => idx,
+            // overwriting index 0 would break the air-at-0 invariant, so fall back to a
+            // new palette entry and repoint those cells instead.
+            None if from_idx != 0 => {
+                self.palette[from_idx] = to;
+                return;
+            }
+            None => {
+                self.palette.push(to);
+                self.palette.len() - 1
+            }
+        };
+        if to_idx == from_idx {
+            return;
+        }
+        for b in &mut self.blocks {
+            if *b == from_idx {
+                *b = to_idx;
+            }
+        }
+    }
+
+    /// A mutable counterpart to [`blocks`](Self::blocks), yielding a handle to each palette index.
+    pub fn blocks_mut(&mut self) -> BlocksMut<'_> {
+        let origin = self.blocks_origin();
+        let size = self.size();
+        BlocksMut {
+            origin,
+            size,
+            inner: self.blocks.iter_mut().enumerate(),
+        }
+    }
+
+    /// Produces a new region covering only the inclusive box from `corner1` to `corner2`.
+    pub fn crop(&self, corner1: Vec3, corner2: Vec3) -> Region<'l> {
+        let mut region = Region::new(self.name.clone(), corner1, corner2);
+        region.blocks = vec![0; region.volume() as usize];
+        for y in region.y_range() {
+            for z in region.z_range() {
+                for x in region.x_range() {
+                    if self.x_range().contains(&x)
+                        && self.y_range().contains(&y)
+                        && self.z_range().contains(&z)
+                    {
+                        let pos = Vec3::new(x, y, z);
+                        let block = self.palette[self.blocks[self.pos_to_index(pos)]].clone();
+                        region.set_block(pos, block);
+                    }
+                }
+            }
+        }
+        for te in &self.tile_entities {
+            let world = self.blocks_origin() + te.pos;
+            if region.x_range().contains(&world.x)
+                && region.y_range().contains(&world.y)
+                && region.z_range().contains(&world.z)
+            {
+                let local = world - region.blocks_origin();
+                let mut te = te.clone();
+                te.pos = UVec3::new(local.x as u32, local.y as u32, local.z as u32);
+                region.tile_entities.push(te);
+            }
+        }
+        for entity in &self.entities {
+            let x = entity.position.x.floor() as i32;
+            let y = entity.position.y.floor() as i32;
+            let z = entity.position.z.floor() as i32;
+            if region.x_range().contains(&x)
+                && region.y_range().contains(&y)
+                && region.z_range().contains(&z)
+            {
+                region.entities.push(entity.clone());
+            }
+        }
+        region
+    }
+
+    /// Stamps every non-air block of `other` into this region, placing its minimum corner at `at`.
+    pub fn paste(&mut self, other: &Region<'l>, at: Vec3) {
+        for (i, &palette_idx) in other.blocks.iter().enumerate() {
+            let block = &other.palette[palette_idx];
+            if block.name == "minecraft:air" {
+                continue;
+            }
+            let pos = (other.index_to_pos(i) - other.blocks_origin()) + at;
+            if self.x_range().contains(&pos.x)
+                && self.y_range().contains(&pos.y)
+                && self.z_range().contains(&pos.z)
+            {
+                self.set_block(pos, block.clone());
+            }
+        }
+    }
+
     pub fn get_tile_entity(&'l self, pos: UVec3) -> Option<&'l TileEntity<'_>> {
         self.tile_entities.iter().find(|e| e.pos == pos)
     }
@@ -206,6 +409,28 @@ impl<'l> Region<'l> {
         self.blocks.iter().filter(|b| b != &&0).count()
     }
 
+    /// Counts how many of each block state the region contains, skipping air.
+    pub fn block_counts(&self) -> HashMap<&BlockState<'l>, usize> {
+        let mut counts = HashMap::new();
+        for &idx in &self.blocks {
+            let state = &self.palette[idx];
+            if state.name == "minecraft:air" {
+                continue;
+            }
+            *counts.entry(state).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    /// Like [`block_counts`](Self::block_counts) but folds every state down to its base `name`.
+    pub fn block_counts_by_name(&self) -> HashMap<&str, usize> {
+        let mut counts = HashMap::new();
+        for (state, n) in self.block_counts() {
+            *counts.entry(state.name.as_ref()).or_insert(0) += n;
+        }
+        counts
+    }
+
     pub fn blocks(&'l self) -> Blocks<'l> {
         Blocks::new(self)
     }
@@ -217,6 +442,124 @@ impl<'l> Region<'l> {
     }
 }
 
+impl<'l> Litematic<'l> {
+    /// Aggregates [`Region::block_counts`] across every region in the litematic.
+    pub fn block_counts(&self) -> HashMap<&BlockState<'l>, usize> {
+        let mut counts = HashMap::new();
+        for region in &self.regions {
+            for (state, n) in region.block_counts() {
+                *counts.entry(state).or_insert(0) += n;
+            }
+        }
+        counts
+    }
+
+    /// Aggregates [`Region::block_counts_by_name`] across every region.
+    pub fn block_counts_by_name(&self) -> HashMap<&str, usize> {
+        let mut counts = HashMap::new();
+        for region in &self.regions {
+            for (name, n) in region.block_counts_by_name() {
+                *counts.entry(name).or_insert(0) += n;
+            }
+        }
+        counts
+    }
+}
+
+fn cell_to_local(index: usize, size: UVec3) -> UVec3 {
+    let i = index as u32;
+    UVec3 {
+        x: i % size.x,
+        z: (i / size.x) % size.z,
+        y: i / (size.z * size.x),
+    }
+}
+
+fn local_to_cell(p: UVec3, size: UVec3) -> usize {
+    (p.y * size.x * size.z + p.z * size.x + p.x) as usize
+}
+
+/// Rewrites the orientation `properties` of a block state for one clockwise Y rotation.
+fn rotate_block_state_y(state: &mut BlockState) {
+    let props = match &mut state.properties {
+        Some(props) => props,
+        None => return,
+    };
+    if let Some(facing) = props.get_mut("facing") {
+        let rotated = match facing.as_ref() {
+            "north" => Some("east"),
+            "east" => Some("south"),
+            "south" => Some("west"),
+            "west" => Some("north"),
+            _ => None,
+        };
+        if let Some(v) = rotated {
+            *facing = Cow::Borrowed(v);
+        }
+    }
+    if let Some(axis) = props.get_mut("axis") {
+        let swapped = match axis.as_ref() {
+            "x" => Some("z"),
+            "z" => Some("x"),
+            _ => None,
+        };
+        if let Some(v) = swapped {
+            *axis = Cow::Borrowed(v);
+        }
+    }
+    if let Some(rotation) = props.get_mut("rotation") {
+        if let Ok(r) = rotation.parse::<i32>() {
+            *rotation = Cow::Owned((r + 4).rem_euclid(16).to_string());
+        }
+    }
+}
+
+/// Rewrites the orientation `properties` of a block state for a mirror across `axis`.
+fn mirror_block_state(state: &mut BlockState, axis: Axis) {
+    let props = match &mut state.properties {
+        Some(props) => props,
+        None => return,
+    };
+    if let Some(facing) = props.get_mut("facing") {
+        let flipped = match (axis, facing.as_ref()) {
+            (Axis::X, "east") => Some("west"),
+            (Axis::X, "west") => Some("east"),
+            (Axis::Z, "north") => Some("south"),
+            (Axis::Z, "south") => Some("north"),
+            (Axis::Y, "up") => Some("down"),
+            (Axis::Y, "down") => Some("up"),
+            _ => None,
+        };
+        if let Some(v) = flipped {
+            *facing = Cow::Borrowed(v);
+        }
+    }
+    if let Some(rotation) = props.get_mut("rotation") {
+        if let Ok(r) = rotation.parse::<i32>() {
+            let mirrored = match axis {
+                Axis::X => (16 - r).rem_euclid(16),
+                Axis::Z => (8 - r).rem_euclid(16),
+                Axis::Y => r,
+            };
+            *rotation = Cow::Owned(mirrored.to_string());
+        }
+    }
+    if matches!(axis, Axis::Y) {
+        for key in ["half", "type"] {
+            if let Some(value) = props.get_mut(key) {
+                let flipped = match value.as_ref() {
+                    "top" => Some("bottom"),
+                    "bottom" => Some("top"),
+                    _ => None,
+                };
+                if let Some(v) = flipped {
+                    *value = Cow::Borrowed(v);
+                }
+            }
+        }
+    }
+}
+
 pub struct Blocks<'b> {
     region: &'b Region<'b>,
     index: usize,
@@ -246,12 +589,50 @@ impl<'b> Iterator for Blocks<'b> {
     }
 }
 
+pub struct BlocksMut<'b> {
+    origin: Vec3,
+    size: UVec3,
+    inner: std::iter::Enumerate<std::slice::IterMut<'b, usize>>,
+}
+
+impl<'b> Iterator for BlocksMut<'b> {
+    type Item = (Vec3, &'b mut usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (index, palette_idx) = self.inner.next()?;
+        let pos = self.origin + cell_to_local(index, self.size);
+        Some((pos, palette_idx))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::borrow::Cow;
+    use std::collections::{BTreeMap, HashMap};
+
+    use super::{mirror_block_state, rotate_block_state_y};
+    use crate::util::{Axis, UVec3, Vec3};
+    use crate::{BlockState, Region, TileEntity};
+
+    fn facing(name: &'static str, facing: &'static str) -> BlockState<'static> {
+        let mut properties = BTreeMap::new();
+        properties.insert(Cow::Borrowed("facing"), Cow::Borrowed(facing));
+        BlockState {
+            name: Cow::Borrowed(name),
+            properties: Some(properties),
+        }
+    }
 
-    use crate::util::Vec3;
-    use crate::Region;
+    fn facing_of(state: &BlockState) -> &str {
+        state.properties.as_ref().unwrap()["facing"].as_ref()
+    }
+
+    fn plain(name: &'static str) -> BlockState<'static> {
+        BlockState {
+            name: Cow::Borrowed(name),
+            properties: None,
+        }
+    }
 
     #[test]
     fn test_pos_to_index() {
@@ -290,4 +671,203 @@ mod tests {
         let r = Region::new(Cow::from(""), Vec3::new(0, 0, 0), Vec3::new(384, 76, 204));
         assert_eq!(r.index_to_pos(247584), Vec3::new(29, 3, 28));
     }
+
+    #[test]
+    fn test_rotate_block_state_y() {
+        let mut stairs = facing("minecraft:oak_stairs", "north");
+        for expected in ["east", "south", "west", "north"] {
+            rotate_block_state_y(&mut stairs);
+            assert_eq!(facing_of(&stairs), expected);
+        }
+
+        let mut log = BlockState {
+            name: Cow::Borrowed("minecraft:oak_log"),
+            properties: Some(BTreeMap::from([(Cow::Borrowed("axis"), Cow::Borrowed("x"))])),
+        };
+        rotate_block_state_y(&mut log);
+        assert_eq!(log.properties.as_ref().unwrap()["axis"].as_ref(), "z");
+
+        let mut sign = BlockState {
+            name: Cow::Borrowed("minecraft:oak_sign"),
+            properties: Some(BTreeMap::from([(Cow::Borrowed("rotation"), Cow::Borrowed("14"))])),
+        };
+        rotate_block_state_y(&mut sign);
+        assert_eq!(sign.properties.as_ref().unwrap()["rotation"].as_ref(), "2");
+    }
+
+    #[test]
+    fn test_block_counts() {
+        let oak_north = facing("minecraft:oak_stairs", "north");
+        let oak_east = facing("minecraft:oak_stairs", "east");
+        let mut r = Region::new(Cow::from(""), Vec3::new(0, 0, 0), Vec3::new(1, 1, 1));
+        r.palette = vec![block!(), oak_north.clone(), oak_east.clone()];
+        // one air, two north stairs, one east stair
+        r.blocks = vec![0, 1, 1, 2];
+
+        let counts = r.block_counts();
+        assert_eq!(counts.len(), 2);
+        assert_eq!(counts.get(&oak_north), Some(&2));
+        assert_eq!(counts.get(&oak_east), Some(&1));
+
+        let by_name = r.block_counts_by_name();
+        assert_eq!(by_name.len(), 1);
+        assert_eq!(by_name.get("minecraft:oak_stairs"), Some(&3));
+    }
+
+    #[test]
+    fn test_optimize_palette() {
+        let air = BlockState {
+            name: Cow::Borrowed("minecraft:air"),
+            properties: None,
+        };
+        let stone = BlockState {
+            name: Cow::Borrowed("minecraft:stone"),
+            properties: None,
+        };
+        let mut r = Region::new(Cow::from(""), Vec3::new(0, 0, 0), Vec3::new(1, 1, 1));
+        // index 2 duplicates the stone at index 1, index 3 is dead
+        r.palette = vec![air.clone(), stone.clone(), stone.clone(), air];
+        r.blocks = vec![0, 1, 2, 0];
+
+        r.optimize_palette();
+
+        assert_eq!(r.palette, vec![block!(), stone]);
+        assert_eq!(r.blocks, vec![0, 1, 1, 0]);
+    }
+
+    #[test]
+    fn test_fill_and_blocks_mut() {
+        let stone = plain("minecraft:stone");
+        let mut r = Region::new(Cow::from(""), Vec3::new(0, 0, 0), Vec3::new(1, 1, 1));
+        r.blocks = vec![0; 8];
+
+        r.fill(Vec3::new(0, 0, 0), Vec3::new(1, 1, 0), stone.clone());
+        // the z == 0 slice is four cells, and the palette grew by exactly one entry
+        assert_eq!(r.total_blocks(), 4);
+        assert_eq!(r.palette, vec![block!(), stone.clone()]);
+
+        // clear the bottom layer back to air through the mutable iterator
+        for (pos, idx) in r.blocks_mut() {
+            if pos.y == 0 {
+                *idx = 0;
+            }
+        }
+        assert_eq!(r.total_blocks(), 2);
+    }
+
+    #[test]
+    fn test_replace() {
+        let stone = plain("minecraft:stone");
+        let dirt = plain("minecraft:dirt");
+        let mut r = Region::new(Cow::from(""), Vec3::new(0, 0, 0), Vec3::new(1, 1, 1));
+        r.palette = vec![block!(), stone.clone()];
+        r.blocks = vec![0, 1, 1, 0];
+
+        // dirt is not in the palette yet: a single in-place palette overwrite
+        r.replace(&stone, dirt.clone());
+        assert_eq!(r.palette, vec![block!(), dirt.clone()]);
+        assert_eq!(r.blocks, vec![0, 1, 1, 0]);
+
+        // air already lives at index 0: the dirt cells are repointed at it
+        r.replace(&dirt, block!());
+        assert_eq!(r.blocks, vec![0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_paste() {
+        let stone = plain("minecraft:stone");
+        let mut src = Region::new(Cow::from("src"), Vec3::new(0, 0, 0), Vec3::new(1, 1, 1));
+        src.palette = vec![block!(), stone.clone()];
+        src.blocks = vec![0; 8];
+        src.blocks[0] = 1; // (0, 0, 0)
+        src.blocks[7] = 1; // (1, 1, 1)
+
+        let mut dest = Region::new(Cow::from("dest"), Vec3::new(0, 0, 0), Vec3::new(1, 1, 1));
+        dest.blocks = vec![0; 8];
+
+        dest.paste(&src, Vec3::new(0, 0, 0));
+        assert_eq!(dest.total_blocks(), 2);
+        assert_eq!(dest.palette, vec![block!(), stone]);
+
+        // an offset that carries everything out of bounds is a no-op
+        let before = dest.total_blocks();
+        dest.paste(&src, Vec3::new(10, 10, 10));
+        assert_eq!(dest.total_blocks(), before);
+    }
+
+    #[test]
+    fn test_rotate_y_non_zero_origin() {
+        let mut r = Region::new(Cow::from("r"), Vec3::new(10, 20, 30), Vec3::new(11, 21, 31));
+        r.palette = vec![block!(), plain("minecraft:stone")];
+        r.blocks = vec![0; 8];
+        // a stone and a tile entity sharing the local cell (1, 0, 0)
+        let idx = r.pos_to_index(Vec3::new(11, 20, 30));
+        r.blocks[idx] = 1;
+        r.tile_entities.push(TileEntity {
+            pos: UVec3::new(1, 0, 0),
+            data: HashMap::new(),
+        });
+
+        r.rotate_y(1);
+
+        // the min corner is held fixed and the footprint stays 2×2×2
+        assert_eq!(r.corner1, Vec3::new(10, 20, 30));
+        assert_eq!(r.corner2, Vec3::new(11, 21, 31));
+        assert_eq!(r.total_blocks(), 1);
+        // (1, 0, 0) rotates to (1, 0, 1) in local space, and the tile entity follows
+        assert_eq!(r.tile_entities[0].pos, UVec3::new(1, 0, 1));
+        assert_eq!(r.blocks[r.pos_to_index(Vec3::new(11, 20, 31))], 1);
+    }
+
+    #[test]
+    fn test_crop_rebases_non_zero_origin() {
+        let mut src = Region::new(Cow::from("src"), Vec3::new(10, 10, 10), Vec3::new(11, 11, 11));
+        src.palette = vec![block!(), plain("minecraft:stone")];
+        src.blocks = vec![0; 8];
+        src.blocks[src.pos_to_index(Vec3::new(11, 11, 11))] = 1;
+        // tile entity at the same cell, stored region-local
+        src.tile_entities.push(TileEntity {
+            pos: UVec3::new(1, 1, 1),
+            data: HashMap::new(),
+        });
+
+        let cropped = src.crop(Vec3::new(11, 11, 11), Vec3::new(11, 11, 11));
+
+        assert_eq!(cropped.total_blocks(), 1);
+        // the kept block and its tile entity are rebased to the crop's own origin
+        assert_eq!(cropped.tile_entities.len(), 1);
+        assert_eq!(cropped.tile_entities[0].pos, UVec3::new(0, 0, 0));
+    }
+
+    #[test]
+    fn test_paste_uses_other_local_coords() {
+        let mut src = Region::new(Cow::from("src"), Vec3::new(100, 0, 100), Vec3::new(101, 1, 101));
+        src.palette = vec![block!(), plain("minecraft:stone")];
+        src.blocks = vec![0; 8];
+        src.blocks[src.pos_to_index(Vec3::new(100, 0, 100))] = 1; // local (0, 0, 0)
+
+        let mut dest = Region::new(Cow::from("dest"), Vec3::new(0, 0, 0), Vec3::new(1, 1, 1));
+        dest.blocks = vec![0; 8];
+
+        // `at` places src's corner at the destination origin regardless of src's world pos
+        dest.paste(&src, Vec3::new(0, 0, 0));
+        assert_eq!(dest.total_blocks(), 1);
+        assert_eq!(dest.blocks[dest.pos_to_index(Vec3::new(0, 0, 0))], 1);
+    }
+
+    #[test]
+    fn test_mirror_block_state() {
+        let mut stairs = facing("minecraft:oak_stairs", "east");
+        mirror_block_state(&mut stairs, Axis::X);
+        assert_eq!(facing_of(&stairs), "west");
+        mirror_block_state(&mut stairs, Axis::Z);
+        assert_eq!(facing_of(&stairs), "west");
+
+        let mut slab = BlockState {
+            name: Cow::Borrowed("minecraft:oak_slab"),
+            properties: Some(BTreeMap::from([(Cow::Borrowed("type"), Cow::Borrowed("top"))])),
+        };
+        mirror_block_state(&mut slab, Axis::Y);
+        assert_eq!(slab.properties.as_ref().unwrap()["type"].as_ref(), "bottom");
+    }
 }
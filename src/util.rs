@@ -84,6 +84,15 @@ impl Sub for Vec3 {
     }
 }
 
+/// One of the three coordinate axes, used to pick the plane a [`Region`](crate::Region)
+/// is mirrored across.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Axis {
+    X,
+    Y,
+    Z,
+}
+
 #[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
 pub struct UVec3 {
     pub x: u32,